@@ -1,6 +1,7 @@
 use charms_sdk::data::{
     charm_values, check, sum_token_amount, App, Data, Transaction, UtxoId, B32, NFT, TOKEN,
 };
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
@@ -12,14 +13,65 @@ pub struct GiftCardNftContent {
     pub expiration_date: u64, // Unix timestamp
     pub created_at: u64,       // Unix timestamp
     pub remaining_balance: u64, // Current spendable balance
+    // Parties approved to redeem on the holder's behalf, as (key-hash, approval id) pairs.
+    // Cleared on every transfer; approvals don't survive a change of ownership.
+    #[serde(default)]
+    pub approvals: Vec<(B32, u64)>,
+}
+
+// A brand-signed, off-chain authorization to mint one gift card. Lets a recipient submit
+// the mint transaction themselves instead of the brand broadcasting every issuance.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MintAuthorization {
+    pub brand: String,
+    pub initial_amount: u64,
+    pub expiration_date: u64,
+    pub deadline: u64, // authorization itself expires if spent after this time
+    pub nonce: String, // UtxoId of the funding input; binds the authorization to one spend
+}
+
+impl MintAuthorization {
+    // Canonical byte encoding that gets signed. Each variable-length field is length-prefixed
+    // so distinct field tuples can never collide on the same byte string (a naive delimiter
+    // join would let a `|` inside `brand` or `nonce` forge a signature meant for other terms).
+    fn canonical_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        write_len_prefixed(&mut bytes, self.brand.as_bytes());
+        bytes.extend_from_slice(&self.initial_amount.to_be_bytes());
+        bytes.extend_from_slice(&self.expiration_date.to_be_bytes());
+        bytes.extend_from_slice(&self.deadline.to_be_bytes());
+        write_len_prefixed(&mut bytes, self.nonce.as_bytes());
+        bytes
+    }
+}
+
+// Appends `field`'s length (as a fixed-width prefix) followed by its bytes, so the boundary
+// between consecutive fields in a canonical encoding is never ambiguous.
+fn write_len_prefixed(bytes: &mut Vec<u8>, field: &[u8]) {
+    bytes.extend_from_slice(&(field.len() as u64).to_be_bytes());
+    bytes.extend_from_slice(field);
+}
+
+// A trusted block/median-time value the caller binds to the spending transaction's time-lock,
+// letting the contract reason about "now" without the zk-app itself having a clock.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReferenceTime {
+    pub block_height: u64,
+    pub min_time: u64, // Unix timestamp; a lower bound on the spending transaction's time
 }
 
 pub fn app_contract(app: &App, tx: &Transaction, x: &Data, w: &Data) -> bool {
     let empty = Data::empty();
-    assert_eq!(x, &empty);
+    let reference_time: Option<ReferenceTime> = if x == &empty {
+        None
+    } else {
+        let reference_time = x.value().ok();
+        check!(reference_time.is_some());
+        reference_time
+    };
     match app.tag {
         NFT => {
-            check!(nft_contract_satisfied(app, tx, w))
+            check!(nft_contract_satisfied(app, tx, w, reference_time.as_ref()))
         }
         TOKEN => {
             check!(token_contract_satisfied(app, tx))
@@ -30,28 +82,42 @@ pub fn app_contract(app: &App, tx: &Transaction, x: &Data, w: &Data) -> bool {
 }
 
 // Gift card NFT contract: allows minting new gift cards or transferring existing ones
-fn nft_contract_satisfied(app: &App, tx: &Transaction, w: &Data) -> bool {
+fn nft_contract_satisfied(
+    app: &App,
+    tx: &Transaction,
+    w: &Data,
+    reference_time: Option<&ReferenceTime>,
+) -> bool {
     // Check minting first (most common operation) for early return
-    if can_mint_gift_card_nft(app, tx, w) {
+    if can_mint_gift_card_nft(app, tx, w, reference_time)
+        || can_mint_gift_card_nft_via_authorization(app, tx, w, reference_time)
+    {
         return true;
     }
-    
+
     // Create token_app once and reuse
     let token_app = App {
         tag: TOKEN,
         identity: app.identity.clone(),
         vk: app.vk.clone(),
     };
-    
+
     // Check other operations in order of likelihood
     check!(can_transfer_gift_card_nft(app, tx) ||
-           can_redeem_gift_card(app, tx, &token_app) ||
+           can_merge_gift_card_nft(app, tx) ||
+           can_redeem_gift_card(app, tx, &token_app, w, reference_time) ||
+           can_claim_expired(app, tx, &token_app, reference_time) ||
            can_burn_gift_card(app, tx, &token_app));
     true
 }
 
 // Mint a new gift card NFT (initial creation)
-fn can_mint_gift_card_nft(nft_app: &App, tx: &Transaction, w: &Data) -> bool {
+fn can_mint_gift_card_nft(
+    nft_app: &App,
+    tx: &Transaction,
+    w: &Data,
+    reference_time: Option<&ReferenceTime>,
+) -> bool {
     let w_str: Option<String> = w.value().ok();
     check!(w_str.is_some());
     let w_str = w_str.unwrap();
@@ -84,10 +150,65 @@ fn can_mint_gift_card_nft(nft_app: &App, tx: &Transaction, w: &Data) -> bool {
     
     // Initial balance must match initial_amount
     check!(nft_content.remaining_balance == nft_content.initial_amount);
-    
-    // Expiration date must be in the future
-    // Note: We can't check current time in zk-app, but we enforce it during redemption
-    
+
+    // A card can't be proven to expire in the future without a reference time bound to the spend.
+    check!(before_deadline(reference_time, nft_content.expiration_date));
+
+    true
+}
+
+// Mint a gift card NFT from a brand-signed `MintAuthorization` (lazy mint). Lets the brand
+// authorize issuance off-chain while the recipient actually submits the mint transaction.
+fn can_mint_gift_card_nft_via_authorization(
+    nft_app: &App,
+    tx: &Transaction,
+    w: &Data,
+    reference_time: Option<&ReferenceTime>,
+) -> bool {
+    let witness: Option<(MintAuthorization, Vec<u8>, Vec<u8>)> = w.value().ok();
+    check!(witness.is_some());
+    let (authorization, brand_pubkey, signature) = witness.unwrap();
+
+    // The brand's verifying key must be the one committed into the app identity.
+    check!(hash_bytes(&brand_pubkey) == nft_app.identity);
+    check!(verify_ed25519(
+        &brand_pubkey,
+        &authorization.canonical_bytes(),
+        &signature
+    ));
+
+    // Bind the authorization to a single funding UTXO so it can't be replayed.
+    let Ok(nonce_utxo_id) = UtxoId::from_str(&authorization.nonce) else {
+        return false;
+    };
+    check!(tx.ins.iter().any(|(utxo_id, _)| utxo_id == &nonce_utxo_id));
+
+    let mut nft_iter = charm_values(nft_app, tx.outs.iter());
+    let Some(first_nft) = nft_iter.next() else {
+        return false;
+    };
+
+    // Must mint exactly one NFT (check if there's a second one)
+    if nft_iter.next().is_some() {
+        return false;
+    }
+
+    let nft_content: GiftCardNftContent = match first_nft.value() {
+        Ok(content) => content,
+        Err(_) => return false,
+    };
+
+    // The minted NFT must match exactly what the brand authorized.
+    check!(nft_content.brand == authorization.brand);
+    check!(nft_content.initial_amount == authorization.initial_amount);
+    check!(nft_content.expiration_date == authorization.expiration_date);
+    check!(nft_content.remaining_balance == nft_content.initial_amount);
+
+    // The authorization's deadline and the card's expiry can't be enforced on-chain without a
+    // reference time bound to the spend.
+    check!(before_deadline(reference_time, authorization.deadline));
+    check!(before_deadline(reference_time, authorization.expiration_date));
+
     true
 }
 
@@ -116,18 +237,104 @@ fn can_transfer_gift_card_nft(nft_app: &App, tx: &Transaction) -> bool {
     let input_balance: u64 = input_nfts.iter().map(|nft| nft.remaining_balance).sum();
     let output_balance: u64 = output_nfts.iter().map(|nft| nft.remaining_balance).sum();
     check!(input_balance == output_balance);
-    
+
+    // Approvals don't survive a change of ownership.
+    check!(output_nfts.iter().all(|nft| nft.approvals.is_empty()));
+
+    true
+}
+
+// Merge several same-brand gift card NFTs (e.g. ones received from a split) into one.
+fn can_merge_gift_card_nft(nft_app: &App, tx: &Transaction) -> bool {
+    let input_nfts: Vec<GiftCardNftContent> = charm_values(nft_app, tx.ins.iter().map(|(_, v)| v))
+        .filter_map(|data| data.value().ok())
+        .collect();
+
+    // Merging only makes sense when there's more than one card to consolidate.
+    check!(input_nfts.len() > 1);
+
+    let output_nfts: Vec<GiftCardNftContent> = charm_values(nft_app, tx.outs.iter())
+        .filter_map(|data| data.value().ok())
+        .collect();
+    check!(output_nfts.len() == 1);
+
+    check!(merge_is_valid(&input_nfts, &output_nfts[0]));
+
     true
 }
 
+// Whether `deadline` hasn't passed yet, as measured by a reference time bound to the spend.
+// Expiry can't be enforced on-chain without one, so the absence of a reference time is itself
+// a failure, not a pass-through.
+fn before_deadline(reference_time: Option<&ReferenceTime>, deadline: u64) -> bool {
+    let Some(reference_time) = reference_time else {
+        return false;
+    };
+    reference_time.min_time < deadline
+}
+
+// Whether `output` is the correct consolidation of `inputs`: same brand and artwork, summed
+// balances and initial amounts, and the earliest expiration/creation time of the inputs.
+// Approvals don't survive consolidation into a new card, same as on a plain transfer.
+fn merge_is_valid(inputs: &[GiftCardNftContent], output: &GiftCardNftContent) -> bool {
+    let brand = &inputs[0].brand;
+    let image = &inputs[0].image;
+    if !inputs.iter().all(|nft| &nft.brand == brand && &nft.image == image) {
+        return false;
+    }
+    if &output.brand != brand || &output.image != image {
+        return false;
+    }
+
+    let input_balance: u64 = inputs.iter().map(|nft| nft.remaining_balance).sum();
+    let input_initial: u64 = inputs.iter().map(|nft| nft.initial_amount).sum();
+    if output.remaining_balance != input_balance || output.initial_amount != input_initial {
+        return false;
+    }
+
+    if !output.approvals.is_empty() {
+        return false;
+    }
+
+    // The merged card can't outlive its earliest-expiring component.
+    let min_expiration = inputs.iter().map(|nft| nft.expiration_date).min().unwrap();
+    let min_created_at = inputs.iter().map(|nft| nft.created_at).min().unwrap();
+    output.expiration_date == min_expiration && output.created_at == min_created_at
+}
+
 pub(crate) fn hash(data: &str) -> B32 {
     let hash = Sha256::digest(data);
     B32(hash.into())
 }
 
+pub(crate) fn hash_bytes(data: &[u8]) -> B32 {
+    let hash = Sha256::digest(data);
+    B32(hash.into())
+}
+
+// Verify an ed25519 signature over `message`, as used by pre-signed mint authorizations and
+// approved-party redemptions.
+pub(crate) fn verify_ed25519(pubkey_bytes: &[u8], message: &[u8], signature_bytes: &[u8]) -> bool {
+    let Ok(pubkey_arr) = <[u8; 32]>::try_from(pubkey_bytes) else {
+        return false;
+    };
+    let Ok(verifying_key) = VerifyingKey::from_bytes(&pubkey_arr) else {
+        return false;
+    };
+    let Ok(sig_arr) = <[u8; 64]>::try_from(signature_bytes) else {
+        return false;
+    };
+    let signature = Signature::from_bytes(&sig_arr);
+    verifying_key.verify(message, &signature).is_ok()
+}
+
 // Gift card token contract: manages fungible token balance
 fn token_contract_satisfied(token_app: &App, tx: &Transaction) -> bool {
-    check!(can_transfer_tokens(token_app, tx) || can_mint_initial_tokens(token_app, tx));
+    check!(
+        can_transfer_tokens(token_app, tx)
+            || can_mint_initial_tokens(token_app, tx)
+            || can_burn_redeemed_tokens(token_app, tx)
+    );
     true
 }
 
@@ -214,33 +421,93 @@ fn can_transfer_tokens(token_app: &App, tx: &Transaction) -> bool {
         let output_nft_balance: u64 = output_nfts.iter().map(|nft| nft.remaining_balance).sum();
         check!(output_nft_balance == output_token_amount);
     }
-    
+
     true
 }
 
-// Redeem gift card (decrease balance)
-fn can_redeem_gift_card(nft_app: &App, tx: &Transaction, token_app: &App) -> bool {
-    // Get NFT content from inputs and outputs
-    let input_nfts: Vec<GiftCardNftContent> = charm_values(nft_app, tx.ins.iter().map(|(_, v)| v))
+// Burn the redeemed share of a gift card's balance: a single input card, and the token total
+// strictly decreasing to the combined balance of the change cards in output. Zero change cards
+// are accepted here (output tokens must then be 0); can_redeem_gift_card's NFT side needs at
+// least one.
+fn can_burn_redeemed_tokens(token_app: &App, tx: &Transaction) -> bool {
+    let nft_app = App {
+        tag: NFT,
+        identity: token_app.identity.clone(),
+        vk: token_app.vk.clone(),
+    };
+
+    let input_nfts: Vec<GiftCardNftContent> = charm_values(&nft_app, tx.ins.iter().map(|(_, v)| v))
         .filter_map(|data| data.value().ok())
         .collect();
-    
+    check!(input_nfts.len() == 1);
+    let input_nft = &input_nfts[0];
+
+    let output_nfts: Vec<GiftCardNftContent> = charm_values(&nft_app, tx.outs.iter())
+        .filter_map(|data| data.value().ok())
+        .collect();
+
+    let Some(input_token_amount) = sum_token_amount(token_app, tx.ins.iter().map(|(_, v)| v)).ok() else {
+        return false;
+    };
+    let Some(output_token_amount) = sum_token_amount(token_app, tx.outs.iter()).ok() else {
+        return false;
+    };
+
+    check!(input_token_amount == input_nft.remaining_balance);
+    check!(output_token_amount < input_token_amount);
+
+    let output_nft_balance: u64 = output_nfts.iter().map(|nft| nft.remaining_balance).sum();
+    check!(output_token_amount == output_nft_balance);
+
+    true
+}
+
+// Redeem gift card (decrease balance)
+fn can_redeem_gift_card(
+    nft_app: &App,
+    tx: &Transaction,
+    token_app: &App,
+    w: &Data,
+    reference_time: Option<&ReferenceTime>,
+) -> bool {
+    // Must have exactly one NFT in input, and we need its UTXO id for delegated approvals.
+    let Some((input_utxo_id, input_nft)) = single_input_gift_card_nft(nft_app, tx) else {
+        return false;
+    };
+
+    // A card can't be proven unexpired without a reference time bound to the spend.
+    check!(before_deadline(reference_time, input_nft.expiration_date));
+
+    // NFT must remain in output, possibly split into several change cards.
     let output_nfts: Vec<GiftCardNftContent> = charm_values(nft_app, tx.outs.iter())
         .filter_map(|data| data.value().ok())
         .collect();
-    
-    // Must have NFT in input
-    check!(input_nfts.len() > 0);
-    
-    // NFT must remain in output (we're redeeming balance, not transferring NFT)
-    check!(output_nfts.len() > 0);
-    
-    let input_nft = &input_nfts[0];
-    let output_nft = &output_nfts[0];
-    
-    // Remaining balance must decrease
-    check!(output_nft.remaining_balance < input_nft.remaining_balance);
-    
+    let Some(output_balance) = redemption_change_total(&input_nft, &output_nfts) else {
+        return false;
+    };
+    let amount_to_redeem = input_nft.remaining_balance - output_balance;
+
+    // A redemption authorized by an approved party (e.g. a merchant "charge this card" flow)
+    // must present a signature over this exact spend, including the resulting approvals,
+    // matching an outstanding approval. Approvals are carried over unchanged rather than
+    // left to the approver to rewrite, or a single-use approval could mint itself a brand
+    // new, unlimited approval on the change card.
+    if let Some((approver_pubkey, approval_id, signature)) =
+        w.value::<(Vec<u8>, u64, Vec<u8>)>().ok()
+    {
+        let approver_hash = hash_bytes(&approver_pubkey);
+        check!(approval_authorizes(&input_nft.approvals, &approver_hash, approval_id));
+        check!(output_nfts.iter().all(|nft| nft.approvals == input_nft.approvals));
+
+        let message = approval_message(
+            &input_utxo_id,
+            amount_to_redeem,
+            approval_id,
+            &input_nft.approvals,
+        );
+        check!(verify_ed25519(&approver_pubkey, &message, &signature));
+    }
+
     // Token amounts must match NFT balances
     let Some(input_token_amount) = sum_token_amount(token_app, tx.ins.iter().map(|(_, v)| v)).ok() else {
         return false;
@@ -248,13 +515,128 @@ fn can_redeem_gift_card(nft_app: &App, tx: &Transaction, token_app: &App) -> boo
     let Some(output_token_amount) = sum_token_amount(token_app, tx.outs.iter()).ok() else {
         return false;
     };
-    
+
     check!(input_token_amount == input_nft.remaining_balance);
-    check!(output_token_amount == output_nft.remaining_balance);
-    
-    // Note: Expiration check would be done off-chain or via additional zk-app logic
-    // For now, we enforce balance conservation
-    
+    check!(output_token_amount == output_balance);
+
+    true
+}
+
+// Validate that `outputs` are valid change cards split off of `input`, and return their
+// combined remaining balance. Returns None if the split isn't well-formed: an empty set of
+// outputs, a mismatched brand/artwork/expiration/initial amount, a zeroed-out change card, or
+// a combined output balance that doesn't leave something behind to redeem.
+fn redemption_change_total(input: &GiftCardNftContent, outputs: &[GiftCardNftContent]) -> Option<u64> {
+    if outputs.is_empty() {
+        return None;
+    }
+
+    let same_card = outputs.iter().all(|nft| {
+        nft.brand == input.brand
+            && nft.image == input.image
+            && nft.expiration_date == input.expiration_date
+            && nft.initial_amount == input.initial_amount
+    });
+    if !same_card {
+        return None;
+    }
+
+    if !outputs.iter().all(|nft| nft.remaining_balance > 0) {
+        return None;
+    }
+
+    let output_balance: u64 = outputs.iter().map(|nft| nft.remaining_balance).sum();
+    if output_balance >= input.remaining_balance {
+        return None;
+    }
+
+    Some(output_balance)
+}
+
+// Find the single gift card NFT spent as an input, along with the UTXO id that carried it.
+// Returns None if there isn't exactly one.
+fn single_input_gift_card_nft(nft_app: &App, tx: &Transaction) -> Option<(String, GiftCardNftContent)> {
+    let mut found = None;
+    for (utxo_id, v) in tx.ins.iter() {
+        let Some(content) = charm_values(nft_app, std::iter::once(v))
+            .next()
+            .and_then(|data| data.value().ok())
+        else {
+            continue;
+        };
+        if found.is_some() {
+            return None;
+        }
+        found = Some((utxo_id.to_string(), content));
+    }
+    found
+}
+
+// Whether `approvals` grants `approver_hash` the right to redeem under `approval_id`.
+fn approval_authorizes(approvals: &[(B32, u64)], approver_hash: &B32, approval_id: u64) -> bool {
+    approvals
+        .iter()
+        .any(|(hash, id)| hash == approver_hash && *id == approval_id)
+}
+
+// Canonical bytes signed by an approved party authorizing a delegated redemption. Binding
+// the resulting `approvals` into the message means the approver can only attest to a
+// redemption that carries the approvals list over unchanged, never one that rewrites it.
+fn approval_message(
+    utxo_id: &str,
+    amount_to_redeem: u64,
+    approval_id: u64,
+    approvals: &[(B32, u64)],
+) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    write_len_prefixed(&mut bytes, utxo_id.as_bytes());
+    bytes.extend_from_slice(&amount_to_redeem.to_be_bytes());
+    bytes.extend_from_slice(&approval_id.to_be_bytes());
+    bytes.extend_from_slice(&(approvals.len() as u64).to_be_bytes());
+    for (hash, id) in approvals {
+        bytes.extend_from_slice(&hash.0);
+        bytes.extend_from_slice(&id.to_be_bytes());
+    }
+    bytes
+}
+
+// Expiry settlement: once a bound reference time shows the card has expired, the NFT is
+// burned and its remaining balance is released in full as plain tokens. Only the output token
+// total is checked; which outputs receive the tokens is not restricted.
+fn can_claim_expired(
+    nft_app: &App,
+    tx: &Transaction,
+    token_app: &App,
+    reference_time: Option<&ReferenceTime>,
+) -> bool {
+    // Expiry can't be proven on-chain without a reference time bound to the spend.
+    let Some(reference_time) = reference_time else {
+        return false;
+    };
+
+    let input_nfts: Vec<GiftCardNftContent> = charm_values(nft_app, tx.ins.iter().map(|(_, v)| v))
+        .filter_map(|data| data.value().ok())
+        .collect();
+    check!(input_nfts.len() == 1);
+    let input_nft = &input_nfts[0];
+    check!(reference_time.min_time >= input_nft.expiration_date);
+
+    // The gift card NFT is consumed; it no longer exists once it's settled.
+    let output_nfts: Vec<GiftCardNftContent> = charm_values(nft_app, tx.outs.iter())
+        .filter_map(|data| data.value().ok())
+        .collect();
+    check!(output_nfts.is_empty());
+
+    let Some(input_token_amount) = sum_token_amount(token_app, tx.ins.iter().map(|(_, v)| v)).ok() else {
+        return false;
+    };
+    check!(input_token_amount == input_nft.remaining_balance);
+
+    let Some(output_token_amount) = sum_token_amount(token_app, tx.outs.iter()).ok() else {
+        return false;
+    };
+    check!(output_token_amount == input_nft.remaining_balance);
+
     true
 }
 
@@ -294,6 +676,7 @@ fn can_burn_gift_card(nft_app: &App, tx: &Transaction, token_app: &App) -> bool
 mod test {
     use super::*;
     use charms_sdk::data::UtxoId;
+    use ed25519_dalek::{Signer, SigningKey};
 
     #[test]
     fn dummy() {}
@@ -307,4 +690,190 @@ mod test {
         let expected = "f54f6d40bd4ba808b188963ae5d72769ad5212dd1d29517ecc4063dd9f033faa";
         assert_eq!(&hash(&data).to_string(), expected);
     }
+
+    fn sample_authorization() -> MintAuthorization {
+        MintAuthorization {
+            brand: "Acme".to_string(),
+            initial_amount: 100,
+            expiration_date: 2_000_000_000,
+            deadline: 1_900_000_000,
+            nonce: "dc78b09d767c8565c4a58a95e7ad5ee22b28fc1685535056a395dc94929cdd5f:1".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_canonical_bytes_rejects_field_boundary_ambiguity() {
+        // Joined naively with `|`, both authorizations read
+        // "brand|100|1|2000000000|1900000000|<nonce>"; length-prefixing must keep these
+        // distinguishable instead of colliding on the same byte string.
+        let a = MintAuthorization {
+            brand: "brand|100".to_string(),
+            initial_amount: 1,
+            ..sample_authorization()
+        };
+        let b = MintAuthorization {
+            brand: "brand".to_string(),
+            initial_amount: 100,
+            expiration_date: 1,
+            deadline: 2_000_000_000,
+            nonce: "1900000000|".to_string() + &sample_authorization().nonce,
+        };
+        assert_ne!(a.canonical_bytes(), b.canonical_bytes());
+    }
+
+    #[test]
+    fn test_verify_ed25519_accepts_valid_and_rejects_tampered() {
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let verifying_key = signing_key.verifying_key();
+        let authorization = sample_authorization();
+        let message = authorization.canonical_bytes();
+        let signature = signing_key.sign(&message);
+
+        assert!(verify_ed25519(
+            verifying_key.as_bytes(),
+            &message,
+            &signature.to_bytes()
+        ));
+
+        // Tampering with the signed terms must invalidate the signature.
+        let tampered = MintAuthorization {
+            initial_amount: authorization.initial_amount + 1,
+            ..authorization
+        };
+        assert!(!verify_ed25519(
+            verifying_key.as_bytes(),
+            &tampered.canonical_bytes(),
+            &signature.to_bytes()
+        ));
+    }
+
+    #[test]
+    fn test_approval_authorizes_matches_hash_and_id() {
+        let approver_hash = hash("merchant-key");
+        let approvals = vec![(approver_hash.clone(), 42)];
+
+        assert!(approval_authorizes(&approvals, &approver_hash, 42));
+        assert!(!approval_authorizes(&approvals, &approver_hash, 43));
+        assert!(!approval_authorizes(&approvals, &hash("someone-else"), 42));
+    }
+
+    #[test]
+    fn test_approval_message_commits_to_approvals() {
+        let approver_hash = hash("merchant-key");
+        let original = vec![(approver_hash.clone(), 1)];
+        let rewritten = vec![(approver_hash.clone(), 1), (hash("attacker-key"), 999)];
+
+        // A signature over the original approvals must not also validate a message where
+        // the approver slipped themselves (or anyone) an extra approval.
+        assert_ne!(
+            approval_message("utxo:0", 10, 1, &original),
+            approval_message("utxo:0", 10, 1, &rewritten)
+        );
+    }
+
+    fn sample_card() -> GiftCardNftContent {
+        GiftCardNftContent {
+            brand: "Acme".to_string(),
+            image: "acme.png".to_string(),
+            initial_amount: 100,
+            expiration_date: 2_000_000_000,
+            created_at: 1_000,
+            remaining_balance: 100,
+            approvals: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_merge_is_valid_accepts_matching_brand_and_sums_balances() {
+        let a = GiftCardNftContent {
+            remaining_balance: 30,
+            initial_amount: 30,
+            created_at: 500,
+            expiration_date: 3_000_000_000,
+            ..sample_card()
+        };
+        let b = GiftCardNftContent {
+            remaining_balance: 70,
+            initial_amount: 70,
+            created_at: 1_000,
+            expiration_date: 2_000_000_000,
+            ..sample_card()
+        };
+        let output = GiftCardNftContent {
+            remaining_balance: 100,
+            initial_amount: 100,
+            created_at: 500,
+            expiration_date: 2_000_000_000, // the earlier of the two inputs' expirations
+            ..sample_card()
+        };
+        assert!(merge_is_valid(&[a, b], &output));
+    }
+
+    #[test]
+    fn test_merge_is_valid_rejects_mismatched_brand() {
+        let a = sample_card();
+        let b = GiftCardNftContent {
+            brand: "Other".to_string(),
+            ..sample_card()
+        };
+        let output = GiftCardNftContent {
+            remaining_balance: a.remaining_balance + b.remaining_balance,
+            initial_amount: a.initial_amount + b.initial_amount,
+            ..sample_card()
+        };
+        assert!(!merge_is_valid(&[a, b], &output));
+    }
+
+    #[test]
+    fn test_merge_is_valid_rejects_output_with_surviving_approvals() {
+        let a = GiftCardNftContent {
+            remaining_balance: 30,
+            initial_amount: 30,
+            approvals: vec![(hash("approver"), 1)],
+            ..sample_card()
+        };
+        let b = GiftCardNftContent {
+            remaining_balance: 70,
+            initial_amount: 70,
+            ..sample_card()
+        };
+        let output = GiftCardNftContent {
+            remaining_balance: 100,
+            initial_amount: 100,
+            approvals: vec![(hash("approver"), 1)],
+            ..sample_card()
+        };
+        assert!(!merge_is_valid(&[a, b], &output));
+    }
+
+    #[test]
+    fn test_redemption_change_total_allows_split_with_change() {
+        let input = sample_card();
+        let change = GiftCardNftContent {
+            remaining_balance: 40,
+            ..sample_card()
+        };
+        assert_eq!(redemption_change_total(&input, &[change]), Some(40));
+    }
+
+    #[test]
+    fn test_redemption_change_total_rejects_outputs_leaving_nothing_to_redeem() {
+        let input = sample_card();
+        let change = GiftCardNftContent {
+            remaining_balance: 100,
+            ..sample_card()
+        };
+        assert_eq!(redemption_change_total(&input, &[change]), None);
+    }
+
+    #[test]
+    fn test_before_deadline_requires_a_reference_time() {
+        let reference_time = ReferenceTime {
+            block_height: 100,
+            min_time: 1_000,
+        };
+        assert!(before_deadline(Some(&reference_time), 1_001));
+        assert!(!before_deadline(Some(&reference_time), 1_000));
+        assert!(!before_deadline(None, 1_001));
+    }
 }